@@ -0,0 +1,84 @@
+//
+// Copyright © 2025 Agora
+// This file is part of TEN Framework, an open source project.
+// Licensed under the Apache License, Version 2.0, with certain conditions.
+// Refer to the "LICENSE" file in the root directory for more information.
+//
+use std::collections::{HashMap, HashSet};
+
+use actix_web::HttpRequest;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// A capability a caller can hold against a specific graph. Handlers
+/// declare the `Permission` they require and check it against the
+/// caller's `RequestContext` before taking a write lock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Permission {
+    GraphRead(Uuid),
+    GraphModify(Uuid),
+}
+
+/// The authenticated identity and resolved permissions for one Designer
+/// request.
+#[derive(Debug, Clone, Default)]
+pub struct RequestContext {
+    pub identity: String,
+    pub permissions: HashSet<Permission>,
+}
+
+impl RequestContext {
+    pub fn allows(&self, permission: &Permission) -> bool {
+        self.permissions.contains(permission)
+    }
+}
+
+/// Maps an opaque, server-issued session token to the identity and
+/// permissions it carries. Populated by whatever login/token-issuance
+/// path authenticates a caller against the real identity backend —
+/// never from anything the caller sends on the request being
+/// authorized. Looking a token up here is the only way a request gets
+/// anything beyond the empty, anonymous `RequestContext`.
+#[derive(Debug, Default)]
+pub struct SessionStore {
+    sessions: RwLock<HashMap<String, RequestContext>>,
+}
+
+impl SessionStore {
+    /// Records the identity and permissions a verified session token is
+    /// allowed to act as. Called by the authentication backend once it
+    /// has verified the caller, not by request handlers.
+    pub async fn register(&self, token: String, context: RequestContext) {
+        self.sessions.write().await.insert(token, context);
+    }
+
+    pub async fn revoke(&self, token: &str) {
+        self.sessions.write().await.remove(token);
+    }
+
+    /// Resolves `req` to a `RequestContext` by looking up the bearer
+    /// token in `Authorization` against this store. A missing token, a
+    /// malformed header, or a token this store doesn't recognize all
+    /// resolve to the anonymous, no-permissions default, so callers
+    /// fail closed rather than trusting anything the request itself
+    /// claims about who it is.
+    pub async fn resolve(&self, req: &HttpRequest) -> RequestContext {
+        let Some(token) = bearer_token(req) else {
+            return RequestContext::default();
+        };
+
+        self.sessions
+            .read()
+            .await
+            .get(token)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+fn bearer_token(req: &HttpRequest) -> Option<&str> {
+    req.headers()
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+}
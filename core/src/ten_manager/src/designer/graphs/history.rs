@@ -0,0 +1,192 @@
+//
+// Copyright © 2025 Agora
+// This file is part of TEN Framework, an open source project.
+// Licensed under the Apache License, Version 2.0, with certain conditions.
+// Refer to the "LICENSE" file in the root directory for more information.
+//
+use ten_rust::graph::Graph;
+
+/// One successful mutation of a graph, captured as the full
+/// before/after snapshot that `graph_delete_extension_node` and friends
+/// already clone in order to roll back on validation failure. Reusing
+/// that snapshot as the undo/redo payload avoids having to derive a
+/// fine-grained inverse for every operation kind.
+pub struct GraphRevision {
+    pub revision: u64,
+    pub before: Graph,
+    pub after: Graph,
+}
+
+/// The undo/redo stack for a single graph, plus the monotonically
+/// increasing revision number new mutations are checked against for
+/// optimistic concurrency.
+#[derive(Default)]
+pub struct GraphCommandHistory {
+    pub revision: u64,
+    undo_stack: Vec<GraphRevision>,
+    redo_stack: Vec<GraphRevision>,
+}
+
+impl GraphCommandHistory {
+    /// Records a successful mutation, advancing the revision counter and
+    /// clearing the redo stack (a fresh edit invalidates any previously
+    /// undone future).
+    pub fn push(&mut self, before: Graph, after: Graph) -> u64 {
+        self.revision += 1;
+        self.undo_stack.push(GraphRevision {
+            revision: self.revision,
+            before,
+            after,
+        });
+        self.redo_stack.clear();
+        self.revision
+    }
+
+    /// Pops the most recent mutation so its `before` snapshot can be
+    /// restored, moving it onto the redo stack.
+    pub fn undo(&mut self) -> Option<GraphRevision> {
+        let entry = self.undo_stack.pop()?;
+        self.revision = self.revision.saturating_sub(1);
+        let restored = GraphRevision {
+            revision: entry.revision,
+            before: entry.before.clone(),
+            after: entry.after.clone(),
+        };
+        self.redo_stack.push(entry);
+        Some(restored)
+    }
+
+    /// Pops the most recently undone mutation so its `after` snapshot
+    /// can be re-applied, moving it back onto the undo stack.
+    pub fn redo(&mut self) -> Option<GraphRevision> {
+        let entry = self.redo_stack.pop()?;
+        self.revision += 1;
+        self.undo_stack.push(GraphRevision {
+            revision: self.revision,
+            before: entry.before.clone(),
+            after: entry.after.clone(),
+        });
+        Some(entry)
+    }
+
+    /// Reverses a just-completed `undo()` call. Callers that validate
+    /// the restored snapshot *after* calling `undo()` and find it
+    /// invalid must call this before returning, or the revision/stack
+    /// state ends up permanently ahead of the graph, which was never
+    /// actually rolled back.
+    pub fn cancel_undo(&mut self) {
+        if let Some(entry) = self.redo_stack.pop() {
+            self.revision += 1;
+            self.undo_stack.push(entry);
+        }
+    }
+
+    /// Reverses a just-completed `redo()` call, for the same reason as
+    /// `cancel_undo`.
+    pub fn cancel_redo(&mut self) {
+        if let Some(entry) = self.undo_stack.pop() {
+            self.revision = self.revision.saturating_sub(1);
+            self.redo_stack.push(entry);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_advances_revision_and_clears_redo_stack() {
+        let mut history = GraphCommandHistory::default();
+        assert_eq!(history.revision, 0);
+
+        let r1 = history.push(Graph::default(), Graph::default());
+        assert_eq!(r1, 1);
+        assert_eq!(history.revision, 1);
+
+        // Undo then push again: the pushed redo entry must be dropped,
+        // since a fresh edit invalidates any previously undone future.
+        assert!(history.undo().is_some());
+        assert_eq!(history.revision, 0);
+
+        let r2 = history.push(Graph::default(), Graph::default());
+        assert_eq!(r2, 1);
+        assert_eq!(history.revision, 1);
+        assert!(history.redo().is_none());
+    }
+
+    #[test]
+    fn undo_then_redo_round_trips_revision() {
+        let mut history = GraphCommandHistory::default();
+        history.push(Graph::default(), Graph::default());
+        history.push(Graph::default(), Graph::default());
+        assert_eq!(history.revision, 2);
+
+        let undone = history.undo().expect("expected an undo entry");
+        assert_eq!(undone.revision, 2);
+        assert_eq!(history.revision, 1);
+
+        let redone = history.redo().expect("expected a redo entry");
+        assert_eq!(redone.revision, 2);
+        assert_eq!(history.revision, 2);
+    }
+
+    #[test]
+    fn undo_on_empty_history_returns_none_and_does_not_underflow_revision() {
+        let mut history = GraphCommandHistory::default();
+        assert!(history.undo().is_none());
+        assert_eq!(history.revision, 0);
+    }
+
+    #[test]
+    fn redo_on_empty_redo_stack_returns_none() {
+        let mut history = GraphCommandHistory::default();
+        history.push(Graph::default(), Graph::default());
+        assert!(history.redo().is_none());
+    }
+
+    #[test]
+    fn cancel_undo_restores_revision_and_undo_stack() {
+        let mut history = GraphCommandHistory::default();
+        history.push(Graph::default(), Graph::default());
+        history.push(Graph::default(), Graph::default());
+
+        assert!(history.undo().is_some());
+        assert_eq!(history.revision, 1);
+
+        // As if the restored candidate had failed validation: the
+        // revision/stacks must end up exactly where they were before
+        // the rejected undo.
+        history.cancel_undo();
+        assert_eq!(history.revision, 2);
+        assert!(history.redo().is_none());
+
+        let undone = history.undo().expect("undo stack should be intact");
+        assert_eq!(undone.revision, 2);
+    }
+
+    #[test]
+    fn cancel_redo_restores_revision_and_redo_stack() {
+        let mut history = GraphCommandHistory::default();
+        history.push(Graph::default(), Graph::default());
+        history.undo();
+        assert_eq!(history.revision, 0);
+
+        assert!(history.redo().is_some());
+        assert_eq!(history.revision, 1);
+
+        // As if the reapplied candidate had failed validation.
+        history.cancel_redo();
+        assert_eq!(history.revision, 0);
+
+        let redone = history.redo().expect("redo stack should be intact");
+        assert_eq!(redone.revision, 1);
+    }
+
+    #[test]
+    fn cancel_undo_on_history_with_no_pending_undo_is_a_no_op() {
+        let mut history = GraphCommandHistory::default();
+        history.cancel_undo();
+        assert_eq!(history.revision, 0);
+    }
+}
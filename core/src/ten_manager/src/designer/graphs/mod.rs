@@ -0,0 +1,30 @@
+//
+// Copyright © 2025 Agora
+// This file is part of TEN Framework, an open source project.
+// Licensed under the Apache License, Version 2.0, with certain conditions.
+// Refer to the "LICENSE" file in the root directory for more information.
+//
+pub mod history;
+pub mod nodes;
+pub mod redo;
+pub mod undo;
+
+use anyhow::Result;
+
+use crate::graph::{GraphInfo, PkgsCache};
+
+/// The call through which a whole in-memory `graph` reaches the
+/// `property.json` entry for `graph_info`. Unlike
+/// `nodes::update_graph_node_in_property_all_fields`, this doesn't
+/// target a single node/action — it's for callers like undo/redo that
+/// swap in an entire snapshot and have no single edit to describe. In
+/// this crate the body only takes its arguments and returns `Ok(())`:
+/// no file is read or written.
+pub fn sync_graph_property_json(
+    pkgs_cache: &mut PkgsCache,
+    graph_info: &mut GraphInfo,
+) -> Result<()> {
+    let _ = (pkgs_cache, graph_info);
+
+    Ok(())
+}
@@ -0,0 +1,296 @@
+//
+// Copyright © 2025 Agora
+// This file is part of TEN Framework, an open source project.
+// Licensed under the Apache License, Version 2.0, with certain conditions.
+// Refer to the "LICENSE" file in the root directory for more information.
+//
+use std::sync::Arc;
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use ten_rust::graph::{
+    connection::GraphConnection, node::GraphNode, Graph,
+};
+
+use crate::{
+    designer::{
+        auth::Permission,
+        response::{
+            ApiResponse, ErrorCode, ErrorResponse, Severity, Status,
+        },
+        DesignerState,
+    },
+    graph::graphs_cache_find_by_id_mut,
+};
+
+use super::super::sync_graph_property_json;
+use super::delete::graph_delete_extension_node;
+
+/// A single step of a batch graph edit. Steps are applied in order
+/// against one cloned `Graph`.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum GraphBatchOperation {
+    DeleteNode {
+        name: String,
+        addon: String,
+        extension_group: Option<String>,
+        app: Option<String>,
+    },
+    AddNode {
+        name: String,
+        addon: String,
+        extension_group: Option<String>,
+        app: Option<String>,
+    },
+    AddConnection {
+        connection: GraphConnection,
+    },
+    RemoveConnection {
+        extension: String,
+        app: Option<String>,
+    },
+    UpdateProperty {
+        name: String,
+        property: serde_json::Value,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GraphBatchRequestPayload {
+    pub graph_id: Uuid,
+    pub operations: Vec<GraphBatchOperation>,
+
+    /// When set, run the operations and report whether they would
+    /// succeed, but never persist to `property.json` or the cache.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GraphBatchResponsePayload {
+    pub success: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GraphBatchFailure {
+    pub failed_operation_index: usize,
+    pub message: String,
+}
+
+/// Applies a batch of graph operations in sequence against a single
+/// cloned `Graph`, running `validate_and_complete_and_flatten` once at
+/// the end. `DeleteNode` steps go through `graph_delete_extension_node`,
+/// which also validates the graph as it stands after that step. If any
+/// operation fails, or the final validation fails, the original graph
+/// is restored and an index is returned: that of the failing operation,
+/// or `operations.len()` when only the final validation failed.
+pub fn apply_graph_operations(
+    graph: &mut Graph,
+    operations: &[GraphBatchOperation],
+) -> std::result::Result<(), (usize, anyhow::Error)> {
+    let original_graph = graph.clone();
+
+    for (index, operation) in operations.iter().enumerate() {
+        let result = match operation {
+            GraphBatchOperation::DeleteNode {
+                name,
+                addon,
+                extension_group,
+                app,
+            } => graph_delete_extension_node(
+                graph,
+                name.clone(),
+                addon.clone(),
+                app.clone(),
+                extension_group.clone(),
+                None,
+            )
+            .and_then(|removed| {
+                if removed {
+                    Ok(())
+                } else {
+                    Err(anyhow!("Node '{name}' not found"))
+                }
+            }),
+            GraphBatchOperation::AddNode {
+                name,
+                addon,
+                extension_group,
+                app,
+            } => {
+                graph.nodes.push(GraphNode {
+                    type_: ten_rust::graph::node::GraphNodeType::Extension,
+                    name: name.clone(),
+                    addon: Some(addon.clone()),
+                    extension_group: extension_group.clone(),
+                    app: app.clone(),
+                    ..Default::default()
+                });
+                Ok(())
+            }
+            GraphBatchOperation::AddConnection { connection } => {
+                graph
+                    .connections
+                    .get_or_insert_with(Vec::new)
+                    .push(connection.clone());
+                Ok(())
+            }
+            GraphBatchOperation::RemoveConnection { extension, app } => {
+                if let Some(connections) = &mut graph.connections {
+                    connections.retain(|conn| {
+                        !((conn.loc.extension.as_ref() == Some(extension))
+                            && &conn.loc.app == app)
+                    });
+                }
+                Ok(())
+            }
+            GraphBatchOperation::UpdateProperty { name, property } => {
+                match graph.nodes.iter_mut().find(|n| &n.name == name) {
+                    Some(node) => {
+                        node.property = Some(property.clone());
+                        Ok(())
+                    }
+                    None => Err(anyhow!("Node '{name}' not found")),
+                }
+            }
+        };
+
+        if let Err(e) = result {
+            *graph = original_graph;
+            return Err((index, e));
+        }
+    }
+
+    if let Err(e) = graph.validate_and_complete_and_flatten(None) {
+        *graph = original_graph;
+        return Err((operations.len(), e));
+    }
+
+    Ok(())
+}
+
+pub async fn graph_batch_endpoint(
+    http_request: HttpRequest,
+    request_payload: web::Json<GraphBatchRequestPayload>,
+    state: web::Data<Arc<DesignerState>>,
+) -> Result<impl Responder, actix_web::Error> {
+    let request_context = state.sessions.resolve(&http_request).await;
+    if !request_context
+        .allows(&Permission::GraphModify(request_payload.graph_id))
+    {
+        let error_response = ErrorResponse {
+            status: Status::Fail,
+            message: format!(
+                "'{}' lacks permission to modify graph '{}'",
+                request_context.identity, request_payload.graph_id
+            ),
+            error: None,
+            severity: Severity::Recoverable,
+            code: ErrorCode::PermissionDenied,
+        };
+        return Ok(HttpResponse::Forbidden().json(error_response));
+    }
+
+    let mut pkgs_cache = state.pkgs_cache.write().await;
+    let mut graphs_cache = state.graphs_cache.write().await;
+
+    let graph_info = match graphs_cache_find_by_id_mut(
+        &mut graphs_cache,
+        &request_payload.graph_id,
+    ) {
+        Some(graph_info) => graph_info,
+        None => {
+            let error_response = ErrorResponse {
+                status: Status::Fail,
+                message: "Graph not found".to_string(),
+                error: None,
+                severity: Severity::Recoverable,
+                code: ErrorCode::GraphNotFound,
+            };
+            return Ok(HttpResponse::NotFound().json(error_response));
+        }
+    };
+
+    if request_payload.dry_run {
+        // Run the operations against a scratch clone so the real graph
+        // and property.json are never touched.
+        let mut scratch_graph = graph_info.graph.clone();
+
+        if let Err((failed_operation_index, err)) = apply_graph_operations(
+            &mut scratch_graph,
+            &request_payload.operations,
+        ) {
+            let error_response = ErrorResponse {
+                status: Status::Fail,
+                message: format!(
+                    "Batch operation {failed_operation_index} failed: {err}"
+                ),
+                error: Some(serde_json::json!(GraphBatchFailure {
+                    failed_operation_index,
+                    message: err.to_string(),
+                })),
+                severity: Severity::Recoverable,
+                code: ErrorCode::ValidationFailed,
+            };
+            return Ok(HttpResponse::BadRequest().json(error_response));
+        }
+
+        let response = ApiResponse {
+            status: Status::Ok,
+            data: GraphBatchResponsePayload { success: true },
+            meta: None,
+        };
+        return Ok(HttpResponse::Ok().json(response));
+    }
+
+    if let Err((failed_operation_index, err)) = apply_graph_operations(
+        &mut graph_info.graph,
+        &request_payload.operations,
+    ) {
+        let error_response = ErrorResponse {
+            status: Status::Fail,
+            message: format!(
+                "Batch operation {failed_operation_index} failed: {err}"
+            ),
+            error: Some(serde_json::json!(GraphBatchFailure {
+                failed_operation_index,
+                message: err.to_string(),
+            })),
+            severity: Severity::Recoverable,
+            code: ErrorCode::ValidationFailed,
+        };
+        return Ok(HttpResponse::BadRequest().json(error_response));
+    }
+
+    // Every operation and the final validation succeeded against the
+    // in-memory graph. Sync property.json from that final graph with
+    // one `sync_graph_property_json` call rather than persisting each
+    // operation individually:
+    // per-operation writes would (a) leave property.json partially
+    // updated if a later operation in the batch failed to persist, and
+    // (b) need a dedicated persistence step for every
+    // `GraphBatchOperation` variant, which `AddConnection`,
+    // `RemoveConnection`, and `UpdateProperty` never got. One sync of
+    // the whole graph covers every variant.
+    if let Err(e) = sync_graph_property_json(&mut pkgs_cache, graph_info) {
+        let error_response = ErrorResponse {
+            status: Status::Fail,
+            message: format!("Failed to update property.json file: {e}"),
+            error: None,
+            severity: Severity::Fatal,
+            code: ErrorCode::PropertyWriteFailed,
+        };
+        return Ok(HttpResponse::InternalServerError().json(error_response));
+    }
+
+    let response = ApiResponse {
+        status: Status::Ok,
+        data: GraphBatchResponsePayload { success: true },
+        meta: None,
+    };
+    Ok(HttpResponse::Ok().json(response))
+}
@@ -6,7 +6,7 @@
 //
 use std::sync::Arc;
 
-use actix_web::{web, HttpResponse, Responder};
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -15,7 +15,10 @@ use ten_rust::graph::{node::GraphNodeType, Graph};
 
 use crate::{
     designer::{
-        response::{ApiResponse, ErrorResponse, Status},
+        auth::Permission,
+        response::{
+            ApiResponse, ErrorCode, ErrorResponse, Severity, Status,
+        },
         DesignerState,
     },
     graph::graphs_cache_find_by_id_mut,
@@ -31,20 +34,78 @@ pub struct DeleteGraphNodeRequestPayload {
     pub addon: String,
     pub extension_group: Option<String>,
     pub app: Option<String>,
+
+    /// When set, run the full delete-and-cleanup logic and report its
+    /// cascade effects, but never persist to `property.json` or the
+    /// cache.
+    #[serde(default)]
+    pub dry_run: bool,
+
+    /// The revision the caller believes the graph is currently at. When
+    /// present, the delete is rejected if another mutation advanced the
+    /// graph past this revision first.
+    #[serde(default)]
+    pub base_revision: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct DeleteGraphNodeResponsePayload {
     pub success: bool,
+    pub revision: u64,
+}
+
+/// Which message flow a pruned or emptied entry belonged to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageFlowKind {
+    Cmd,
+    Data,
+    AudioFrame,
+    VideoFrame,
+}
+
+/// A destination that was removed from a message flow because it pointed
+/// at the deleted node.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PrunedDestination {
+    pub flow_kind: MessageFlowKind,
+    pub from_extension: Option<String>,
+    pub pruned_extension: String,
 }
 
+/// A message flow left with zero destinations after pruning.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EmptiedFlow {
+    pub flow_kind: MessageFlowKind,
+    pub from_extension: Option<String>,
+}
+
+/// Collects the cascade effects of a graph edit so callers (in
+/// particular a `dry_run` request) can report what would happen before
+/// it is committed.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GraphDiagnostics {
+    /// Connections removed outright because they originated at the
+    /// deleted node.
+    pub connections_removed: Vec<String>,
+    pub destinations_pruned: Vec<PrunedDestination>,
+    pub flows_emptied: Vec<EmptiedFlow>,
+    pub validation_errors: Vec<String>,
+}
+
+/// Deletes the matching extension node and cleans up any connections
+/// that referenced it. Returns `Ok(true)` if a node was actually
+/// removed, `Ok(false)` if no node matched (nothing to do), or `Err` if
+/// the resulting graph failed validation (in which case `graph` is
+/// restored to its pre-call state).
 pub fn graph_delete_extension_node(
     graph: &mut Graph,
     pkg_name: String,
     addon: String,
     app: Option<String>,
     extension_group: Option<String>,
-) -> Result<()> {
+    mut diagnostics: Option<&mut GraphDiagnostics>,
+) -> Result<bool> {
     // Store the original state in case validation fails.
     let original_graph = graph.clone();
 
@@ -60,12 +121,23 @@ pub fn graph_delete_extension_node(
 
     // If no node was removed, return early.
     if graph.nodes.len() == original_nodes_len {
-        return Ok(());
+        return Ok(false);
     }
 
     // The node was removed, now clean up any connections.
     if let Some(connections) = &mut graph.connections {
         // 1. Remove entire connections with matching app and extension.
+        if let Some(diagnostics) = diagnostics.as_deref_mut() {
+            for conn in connections.iter() {
+                if conn.loc.extension.as_ref() == Some(&pkg_name)
+                    && conn.loc.app == app
+                {
+                    diagnostics
+                        .connections_removed
+                        .push(pkg_name.clone());
+                }
+            }
+        }
         connections.retain(|conn| {
             !((conn.loc.extension.as_ref() == Some(&pkg_name))
                 && conn.loc.app == app)
@@ -73,14 +145,30 @@ pub fn graph_delete_extension_node(
 
         // 2. Remove destinations from message flows in all connections.
         for connection in connections.iter_mut() {
+            let from_extension = connection.loc.extension.clone();
+
             // Process cmd flows.
             if let Some(cmd_flows) = &mut connection.cmd {
                 for flow in cmd_flows.iter_mut() {
+                    record_pruned_destinations(
+                        diagnostics.as_deref_mut(),
+                        MessageFlowKind::Cmd,
+                        &from_extension,
+                        &flow.dest,
+                        &pkg_name,
+                        &app,
+                    );
                     flow.dest.retain(|dest| {
                         !((dest.loc.extension.as_ref() == Some(&pkg_name))
                             && dest.loc.app == app)
                     });
                 }
+                record_emptied_flows(
+                    diagnostics.as_deref_mut(),
+                    MessageFlowKind::Cmd,
+                    &from_extension,
+                    cmd_flows.iter().filter(|flow| flow.dest.is_empty()).count(),
+                );
                 // Remove empty cmd flows.
                 cmd_flows.retain(|flow| !flow.dest.is_empty());
             }
@@ -88,11 +176,25 @@ pub fn graph_delete_extension_node(
             // Process data flows.
             if let Some(data_flows) = &mut connection.data {
                 for flow in data_flows.iter_mut() {
+                    record_pruned_destinations(
+                        diagnostics.as_deref_mut(),
+                        MessageFlowKind::Data,
+                        &from_extension,
+                        &flow.dest,
+                        &pkg_name,
+                        &app,
+                    );
                     flow.dest.retain(|dest| {
                         !((dest.loc.extension.as_ref() == Some(&pkg_name))
                             && dest.loc.app == app)
                     });
                 }
+                record_emptied_flows(
+                    diagnostics.as_deref_mut(),
+                    MessageFlowKind::Data,
+                    &from_extension,
+                    data_flows.iter().filter(|flow| flow.dest.is_empty()).count(),
+                );
                 // Remove empty data flows.
                 data_flows.retain(|flow| !flow.dest.is_empty());
             }
@@ -100,11 +202,28 @@ pub fn graph_delete_extension_node(
             // Process audio_frame flows.
             if let Some(audio_flows) = &mut connection.audio_frame {
                 for flow in audio_flows.iter_mut() {
+                    record_pruned_destinations(
+                        diagnostics.as_deref_mut(),
+                        MessageFlowKind::AudioFrame,
+                        &from_extension,
+                        &flow.dest,
+                        &pkg_name,
+                        &app,
+                    );
                     flow.dest.retain(|dest| {
                         !((dest.loc.extension.as_ref() == Some(&pkg_name))
                             && dest.loc.app == app)
                     });
                 }
+                record_emptied_flows(
+                    diagnostics.as_deref_mut(),
+                    MessageFlowKind::AudioFrame,
+                    &from_extension,
+                    audio_flows
+                        .iter()
+                        .filter(|flow| flow.dest.is_empty())
+                        .count(),
+                );
                 // Remove empty audio_frame flows.
                 audio_flows.retain(|flow| !flow.dest.is_empty());
             }
@@ -112,11 +231,28 @@ pub fn graph_delete_extension_node(
             // Process video_frame flows.
             if let Some(video_flows) = &mut connection.video_frame {
                 for flow in video_flows.iter_mut() {
+                    record_pruned_destinations(
+                        diagnostics.as_deref_mut(),
+                        MessageFlowKind::VideoFrame,
+                        &from_extension,
+                        &flow.dest,
+                        &pkg_name,
+                        &app,
+                    );
                     flow.dest.retain(|dest| {
                         !((dest.loc.extension.as_ref() == Some(&pkg_name))
                             && dest.loc.app == app)
                     });
                 }
+                record_emptied_flows(
+                    diagnostics.as_deref_mut(),
+                    MessageFlowKind::VideoFrame,
+                    &from_extension,
+                    video_flows
+                        .iter()
+                        .filter(|flow| flow.dest.is_empty())
+                        .count(),
+                );
                 // Remove empty video_frame flows.
                 video_flows.retain(|flow| !flow.dest.is_empty());
             }
@@ -141,8 +277,11 @@ pub fn graph_delete_extension_node(
 
     // Validate the graph.
     match graph.validate_and_complete_and_flatten(None) {
-        Ok(_) => Ok(()),
+        Ok(_) => Ok(true),
         Err(e) => {
+            if let Some(diagnostics) = diagnostics.as_deref_mut() {
+                diagnostics.validation_errors.push(e.to_string());
+            }
             // Restore the original graph if validation fails.
             *graph = original_graph;
             Err(e)
@@ -150,13 +289,79 @@ pub fn graph_delete_extension_node(
     }
 }
 
+/// Records, in `diagnostics`, every destination in `dest` that matches
+/// `pkg_name`/`app` and is about to be pruned.
+fn record_pruned_destinations(
+    mut diagnostics: Option<&mut GraphDiagnostics>,
+    flow_kind: MessageFlowKind,
+    from_extension: &Option<String>,
+    dest: &[ten_rust::graph::connection::GraphDestination],
+    pkg_name: &str,
+    app: &Option<String>,
+) {
+    let Some(diagnostics) = diagnostics.as_deref_mut() else {
+        return;
+    };
+
+    for d in dest {
+        if d.loc.extension.as_ref() == Some(&pkg_name.to_string())
+            && &d.loc.app == app
+        {
+            diagnostics.destinations_pruned.push(PrunedDestination {
+                flow_kind,
+                from_extension: from_extension.clone(),
+                pruned_extension: pkg_name.to_string(),
+            });
+        }
+    }
+}
+
+/// Records, in `diagnostics`, that `count` flows of `flow_kind` ended up
+/// with no destinations left and will be dropped.
+fn record_emptied_flows(
+    diagnostics: Option<&mut GraphDiagnostics>,
+    flow_kind: MessageFlowKind,
+    from_extension: &Option<String>,
+    count: usize,
+) {
+    let Some(diagnostics) = diagnostics else {
+        return;
+    };
+
+    for _ in 0..count {
+        diagnostics.flows_emptied.push(EmptiedFlow {
+            flow_kind,
+            from_extension: from_extension.clone(),
+        });
+    }
+}
+
 pub async fn delete_graph_node_endpoint(
+    http_request: HttpRequest,
     request_payload: web::Json<DeleteGraphNodeRequestPayload>,
     state: web::Data<Arc<DesignerState>>,
 ) -> Result<impl Responder, actix_web::Error> {
+    let request_context = state.sessions.resolve(&http_request).await;
+    if !request_context
+        .allows(&Permission::GraphModify(request_payload.graph_id))
+    {
+        let error_response = ErrorResponse {
+            status: Status::Fail,
+            message: format!(
+                "'{}' lacks permission to modify graph '{}'",
+                request_context.identity, request_payload.graph_id
+            ),
+            error: None,
+            severity: Severity::Recoverable,
+            code: ErrorCode::PermissionDenied,
+        };
+        return Ok(HttpResponse::Forbidden().json(error_response));
+    }
+
     // Get a write lock on the state since we need to modify the graph.
     let mut pkgs_cache = state.pkgs_cache.write().await;
     let mut graphs_cache = state.graphs_cache.write().await;
+    let mut command_history = state.command_history.write().await;
 
     // Get the specified graph from graphs_cache.
     let graph_info = match graphs_cache_find_by_id_mut(
@@ -169,50 +374,248 @@ pub async fn delete_graph_node_endpoint(
                 status: Status::Fail,
                 message: "Graph not found".to_string(),
                 error: None,
+                severity: Severity::Recoverable,
+                code: ErrorCode::GraphNotFound,
             };
             return Ok(HttpResponse::NotFound().json(error_response));
         }
     };
 
-    // Delete the extension node.
-    if let Err(err) = graph_delete_extension_node(
-        &mut graph_info.graph,
-        request_payload.name.clone(),
-        request_payload.addon.clone(),
-        request_payload.app.clone(),
-        request_payload.extension_group.clone(),
-    ) {
-        let error_response = ErrorResponse {
-            status: Status::Fail,
-            message: format!("Failed to delete node: {err}"),
-            error: None,
+    let history = command_history
+        .entry(request_payload.graph_id)
+        .or_default();
+
+    if let Some(base_revision) = request_payload.base_revision {
+        if history.revision != base_revision {
+            let error_response = ErrorResponse {
+                status: Status::Fail,
+                message: format!(
+                    "Graph is at revision {}, but the edit was based on {}",
+                    history.revision, base_revision
+                ),
+                error: None,
+                severity: Severity::Recoverable,
+                code: ErrorCode::RevisionConflict,
+            };
+            return Ok(HttpResponse::Conflict().json(error_response));
+        }
+    }
+
+    if request_payload.dry_run {
+        // Run the full delete-and-cleanup logic against a scratch clone
+        // so the real graph and property.json are never touched.
+        let mut scratch_graph = graph_info.graph.clone();
+        let mut diagnostics = GraphDiagnostics::default();
+
+        // `graph_delete_extension_node` already records validation
+        // failures into `diagnostics` itself; nothing further to push
+        // here. The result is discarded since a dry run never returns
+        // an error response of its own — failure is just reported
+        // through the diagnostics payload.
+        let _ = graph_delete_extension_node(
+            &mut scratch_graph,
+            request_payload.name.clone(),
+            request_payload.addon.clone(),
+            request_payload.app.clone(),
+            request_payload.extension_group.clone(),
+            Some(&mut diagnostics),
+        );
+
+        let response = ApiResponse {
+            status: Status::Ok,
+            data: diagnostics,
+            meta: None,
         };
-        return Ok(HttpResponse::BadRequest().json(error_response));
+        return Ok(HttpResponse::Ok().json(response));
     }
 
-    // Try to update property.json file if possible.
-    if let Err(e) = update_graph_node_in_property_all_fields(
-        &mut pkgs_cache,
-        graph_info,
-        &request_payload.name,
-        &request_payload.addon,
-        &request_payload.extension_group,
-        &request_payload.app,
-        &None,
-        GraphNodeUpdateAction::Delete,
-    ) {
+    let graph_before_edit = graph_info.graph.clone();
+
+    /// Distinguishes which phase failed, so the timeout wrapper can
+    /// still report the same error codes `delete_graph_node_endpoint`
+    /// used before the timeout was introduced.
+    enum DeleteAndPersistError {
+        Validation(anyhow::Error),
+        PropertyWrite(anyhow::Error),
+    }
+
+    // `graph_delete_extension_node` and
+    // `update_graph_node_in_property_all_fields` are both plain
+    // synchronous calls with no `.await` points, so racing them inside
+    // an `async {}` block against `tokio::time::timeout` would never
+    // actually get interrupted: a future that never yields runs to
+    // completion on its first poll, before the timer is ever checked.
+    // `spawn_blocking` moves the work to a blocking-pool thread so the
+    // timeout can race the `JoinHandle` instead. Note this only stops
+    // *waiting* on the work — it does not cancel it, so on a timeout the
+    // blocking task keeps running in the background against its own
+    // clone of the graph/pkgs_cache and its result is discarded.
+    let mut scratch_graph_info = graph_info.clone();
+    let mut scratch_pkgs_cache = pkgs_cache.clone();
+    let name = request_payload.name.clone();
+    let addon = request_payload.addon.clone();
+    let extension_group = request_payload.extension_group.clone();
+    let app = request_payload.app.clone();
+
+    let mut join_handle = tokio::task::spawn_blocking(move || {
+        let node_removed = graph_delete_extension_node(
+            &mut scratch_graph_info.graph,
+            name.clone(),
+            addon.clone(),
+            app.clone(),
+            extension_group.clone(),
+            None,
+        )
+        .map_err(DeleteAndPersistError::Validation)?;
+
+        if node_removed {
+            update_graph_node_in_property_all_fields(
+                &mut scratch_pkgs_cache,
+                &mut scratch_graph_info,
+                &name,
+                &addon,
+                &extension_group,
+                &app,
+                &None,
+                GraphNodeUpdateAction::Delete,
+            )
+            .map_err(DeleteAndPersistError::PropertyWrite)?;
+        }
+
+        Ok::<_, DeleteAndPersistError>((
+            node_removed,
+            scratch_graph_info,
+            scratch_pkgs_cache,
+        ))
+    });
+
+    let node_removed = match tokio::time::timeout(
+        state.operation_timeout,
+        &mut join_handle,
+    )
+    .await
+    {
+        Ok(Ok(Ok((node_removed, new_graph_info, new_pkgs_cache)))) => {
+            *graph_info = new_graph_info;
+            *pkgs_cache = new_pkgs_cache;
+            node_removed
+        }
+        Ok(Ok(Err(DeleteAndPersistError::Validation(err)))) => {
+            // `graph_delete_extension_node` already restores its graph
+            // argument itself on validation failure; the real
+            // `graph_info` was never touched.
+            let error_response = ErrorResponse {
+                status: Status::Fail,
+                message: format!("Failed to delete node: {err}"),
+                error: None,
+                severity: Severity::Recoverable,
+                code: ErrorCode::ValidationFailed,
+            };
+            return Ok(HttpResponse::BadRequest().json(error_response));
+        }
+        Ok(Ok(Err(DeleteAndPersistError::PropertyWrite(err)))) => {
+            // The blocking task's graph already changed, but that was a
+            // clone — the real `graph_info` is untouched, so this is
+            // still recoverable from the caller's point of view.
+            let error_response = ErrorResponse {
+                status: Status::Fail,
+                message: format!(
+                    "Failed to update property.json file: {err}"
+                ),
+                error: None,
+                severity: Severity::Recoverable,
+                code: ErrorCode::PropertyWriteFailed,
+            };
+            return Ok(HttpResponse::BadRequest().json(error_response));
+        }
+        Ok(Err(join_err)) => {
+            let error_response = ErrorResponse {
+                status: Status::Fail,
+                message: format!(
+                    "Delete task panicked: {join_err}"
+                ),
+                error: None,
+                severity: Severity::Fatal,
+                code: ErrorCode::PropertyWriteFailed,
+            };
+            return Ok(
+                HttpResponse::InternalServerError().json(error_response)
+            );
+        }
+        Err(_elapsed) => {
+            // `spawn_blocking` can't be cancelled: the task keeps
+            // running against its own clones and, if validation passes,
+            // still reaches the `update_graph_node_in_property_all_fields`
+            // call after we gave up waiting, which persists the delete
+            // with no corresponding update to `graphs_cache` /
+            // `pkgs_cache` — the caller has already been told it's
+            // safe to retry. We can't stop that write, but we can keep
+            // the `JoinHandle` alive in a detached task and log loudly
+            // if the orphaned write lands, so operators have a trail
+            // to reconcile from instead of a silent divergence.
+            let graph_id = request_payload.graph_id;
+            let node_name = request_payload.name.clone();
+            tokio::spawn(async move {
+                match join_handle.await {
+                    Ok(Ok(_)) => {
+                        tracing::error!(
+                            %graph_id,
+                            name = %node_name,
+                            "delete task completed after its caller \
+                             timed out; property.json may now be out \
+                             of sync with graphs_cache/pkgs_cache",
+                        );
+                    }
+                    Ok(Err(_)) => {
+                        // Validation or the property write failed on
+                        // its own, so no orphaned write landed.
+                    }
+                    Err(join_err) => {
+                        tracing::error!(
+                            %graph_id,
+                            name = %node_name,
+                            %join_err,
+                            "orphaned delete task panicked after its \
+                             caller timed out",
+                        );
+                    }
+                }
+            });
+
+            let error_response = ErrorResponse {
+                status: Status::Fail,
+                message: format!(
+                    "Delete timed out after {:?}",
+                    state.operation_timeout
+                ),
+                error: None,
+                severity: Severity::Recoverable,
+                code: ErrorCode::Timeout,
+            };
+            return Ok(
+                HttpResponse::RequestTimeout().json(error_response)
+            );
+        }
+    };
+
+    if !node_removed {
         let error_response = ErrorResponse {
             status: Status::Fail,
-            message: format!("Failed to update property.json file: {e}"),
+            message: "No matching node found in the graph".to_string(),
             error: None,
+            severity: Severity::Recoverable,
+            code: ErrorCode::NodeNotFound,
         };
-        return Ok(HttpResponse::BadRequest().json(error_response));
+        return Ok(HttpResponse::NotFound().json(error_response));
     }
 
+    let revision =
+        history.push(graph_before_edit, graph_info.graph.clone());
+
     // Return success response
     let response = ApiResponse {
         status: Status::Ok,
-        data: DeleteGraphNodeResponsePayload { success: true },
+        data: DeleteGraphNodeResponsePayload { success: true, revision },
         meta: None,
     };
     Ok(HttpResponse::Ok().json(response))
@@ -0,0 +1,50 @@
+//
+// Copyright © 2025 Agora
+// This file is part of TEN Framework, an open source project.
+// Licensed under the Apache License, Version 2.0, with certain conditions.
+// Refer to the "LICENSE" file in the root directory for more information.
+//
+pub mod batch;
+pub mod delete;
+
+use anyhow::Result;
+
+use crate::graph::{GraphInfo, PkgsCache};
+
+/// Which kind of node edit `update_graph_node_in_property_all_fields` is
+/// syncing to `property.json`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphNodeUpdateAction {
+    Add,
+    Delete,
+    Update,
+}
+
+/// The call through which node edits reach the `property.json` file
+/// backing `graph_info`. In this crate the body only takes its
+/// arguments and returns `Ok(())`: no file is read or written, and
+/// neither `pkgs_cache` nor `graph_info` is changed.
+#[allow(clippy::too_many_arguments)]
+pub fn update_graph_node_in_property_all_fields(
+    pkgs_cache: &mut PkgsCache,
+    graph_info: &mut GraphInfo,
+    node_name: &str,
+    addon: &str,
+    extension_group: &Option<String>,
+    app: &Option<String>,
+    property: &Option<serde_json::Value>,
+    action: GraphNodeUpdateAction,
+) -> Result<()> {
+    let _ = (
+        pkgs_cache,
+        graph_info,
+        node_name,
+        addon,
+        extension_group,
+        app,
+        property,
+        action,
+    );
+
+    Ok(())
+}
@@ -0,0 +1,152 @@
+//
+// Copyright © 2025 Agora
+// This file is part of TEN Framework, an open source project.
+// Licensed under the Apache License, Version 2.0, with certain conditions.
+// Refer to the "LICENSE" file in the root directory for more information.
+//
+use std::sync::Arc;
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    designer::{
+        auth::Permission,
+        response::{ApiResponse, ErrorCode, ErrorResponse, Severity, Status},
+        DesignerState,
+    },
+    graph::graphs_cache_find_by_id_mut,
+};
+
+use super::sync_graph_property_json;
+
+#[derive(Serialize, Deserialize)]
+pub struct RedoGraphRequestPayload {
+    pub graph_id: Uuid,
+
+    /// The revision the caller believes the graph is currently at. The
+    /// redo is rejected if another mutation advanced the graph past
+    /// this revision first.
+    pub base_revision: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RedoGraphResponsePayload {
+    pub revision: u64,
+}
+
+pub async fn redo_graph_endpoint(
+    http_request: HttpRequest,
+    request_payload: web::Json<RedoGraphRequestPayload>,
+    state: web::Data<Arc<DesignerState>>,
+) -> Result<impl Responder, actix_web::Error> {
+    let request_context = state.sessions.resolve(&http_request).await;
+    if !request_context
+        .allows(&Permission::GraphModify(request_payload.graph_id))
+    {
+        let error_response = ErrorResponse {
+            status: Status::Fail,
+            message: format!(
+                "'{}' lacks permission to modify graph '{}'",
+                request_context.identity, request_payload.graph_id
+            ),
+            error: None,
+            severity: Severity::Recoverable,
+            code: ErrorCode::PermissionDenied,
+        };
+        return Ok(HttpResponse::Forbidden().json(error_response));
+    }
+
+    let mut pkgs_cache = state.pkgs_cache.write().await;
+    let mut graphs_cache = state.graphs_cache.write().await;
+    let mut command_history = state.command_history.write().await;
+
+    let graph_info = match graphs_cache_find_by_id_mut(
+        &mut graphs_cache,
+        &request_payload.graph_id,
+    ) {
+        Some(graph_info) => graph_info,
+        None => {
+            let error_response = ErrorResponse {
+                status: Status::Fail,
+                message: "Graph not found".to_string(),
+                error: None,
+                severity: Severity::Recoverable,
+                code: ErrorCode::GraphNotFound,
+            };
+            return Ok(HttpResponse::NotFound().json(error_response));
+        }
+    };
+
+    let history = command_history
+        .entry(request_payload.graph_id)
+        .or_default();
+
+    if history.revision != request_payload.base_revision {
+        let error_response = ErrorResponse {
+            status: Status::Fail,
+            message: format!(
+                "Graph is at revision {}, but the redo was based on {}",
+                history.revision, request_payload.base_revision
+            ),
+            error: None,
+            severity: Severity::Recoverable,
+            code: ErrorCode::RevisionConflict,
+        };
+        return Ok(HttpResponse::Conflict().json(error_response));
+    }
+
+    let Some(reapplied) = history.redo() else {
+        let error_response = ErrorResponse {
+            status: Status::Fail,
+            message: "Nothing to redo".to_string(),
+            error: None,
+            severity: Severity::Recoverable,
+            code: ErrorCode::NothingToRedo,
+        };
+        return Ok(HttpResponse::BadRequest().json(error_response));
+    };
+
+    let mut candidate = reapplied.after;
+    if let Err(e) = candidate.validate_and_complete_and_flatten(None) {
+        // `history.redo()` already popped the stack and advanced the
+        // revision; since the candidate is rejected, `graph_info.graph`
+        // is never touched, so undo that advance too or the history
+        // ends up permanently out of sync with the actual graph.
+        history.cancel_redo();
+        let error_response = ErrorResponse {
+            status: Status::Fail,
+            message: format!("Redo produced an invalid graph: {e}"),
+            error: None,
+            severity: Severity::Recoverable,
+            code: ErrorCode::ValidationFailed,
+        };
+        return Ok(HttpResponse::InternalServerError().json(error_response));
+    }
+    graph_info.graph = candidate;
+
+    // The in-memory graph has already been swapped to the reapplied
+    // snapshot, so a sync failure here leaves the cache and disk
+    // divergent — that's fatal, same as a persist failure on the
+    // forward edit path.
+    if let Err(e) = sync_graph_property_json(&mut pkgs_cache, graph_info) {
+        let error_response = ErrorResponse {
+            status: Status::Fail,
+            message: format!(
+                "Redo applied in memory, but failed to sync property.json: {e}"
+            ),
+            error: None,
+            severity: Severity::Fatal,
+            code: ErrorCode::PropertyWriteFailed,
+        };
+        return Ok(HttpResponse::InternalServerError().json(error_response));
+    }
+
+    let response = ApiResponse {
+        status: Status::Ok,
+        data: RedoGraphResponsePayload { revision: history.revision },
+        meta: None,
+    };
+    Ok(HttpResponse::Ok().json(response))
+}
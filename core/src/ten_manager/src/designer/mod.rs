@@ -0,0 +1,56 @@
+//
+// Copyright © 2025 Agora
+// This file is part of TEN Framework, an open source project.
+// Licensed under the Apache License, Version 2.0, with certain conditions.
+// Refer to the "LICENSE" file in the root directory for more information.
+//
+pub mod auth;
+pub mod graphs;
+pub mod response;
+
+use std::{collections::HashMap, time::Duration};
+
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::designer::auth::SessionStore;
+use crate::graph::{GraphsCache, PkgsCache};
+use graphs::history::GraphCommandHistory;
+
+/// The default for `DesignerState::operation_timeout`: how long
+/// `delete_graph_node_endpoint` waits on validation and the
+/// `property.json` write while holding the `pkgs_cache`/`graphs_cache`
+/// write locks.
+pub const DEFAULT_GRAPH_OPERATION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Shared state handed to every Designer HTTP handler.
+pub struct DesignerState {
+    pub pkgs_cache: RwLock<PkgsCache>,
+    pub graphs_cache: RwLock<GraphsCache>,
+    pub command_history: RwLock<HashMap<Uuid, GraphCommandHistory>>,
+
+    /// Server-side session table that request handlers resolve bearer
+    /// tokens against. The only source of truth for a caller's
+    /// permissions — never the request being authorized itself.
+    pub sessions: SessionStore,
+
+    /// Budget for the validate-and-persist phase of
+    /// `delete_graph_node_endpoint`. When it is exceeded the endpoint
+    /// stops waiting, leaves the cached graph unchanged, releases its
+    /// locks and returns `ErrorCode::Timeout`. The work itself is not
+    /// cancelled and runs to completion in the background. The batch,
+    /// undo and redo endpoints do not apply this budget.
+    pub operation_timeout: Duration,
+}
+
+impl Default for DesignerState {
+    fn default() -> Self {
+        Self {
+            pkgs_cache: RwLock::new(PkgsCache::default()),
+            graphs_cache: RwLock::new(GraphsCache::default()),
+            command_history: RwLock::new(HashMap::new()),
+            sessions: SessionStore::default(),
+            operation_timeout: DEFAULT_GRAPH_OPERATION_TIMEOUT,
+        }
+    }
+}
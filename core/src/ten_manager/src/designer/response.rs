@@ -0,0 +1,58 @@
+//
+// Copyright © 2025 Agora
+// This file is part of TEN Framework, an open source project.
+// Licensed under the Apache License, Version 2.0, with certain conditions.
+// Refer to the "LICENSE" file in the root directory for more information.
+//
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub enum Status {
+    Ok,
+    Fail,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ApiResponse<T> {
+    pub status: Status,
+    pub data: T,
+    pub meta: Option<serde_json::Value>,
+}
+
+/// How badly an error leaves the system. `Recoverable` means the
+/// operation was rejected and nothing changed (or was rolled back);
+/// `Fatal` means the operation partially applied and some piece of
+/// state (typically the in-memory graph vs. `property.json`) is now
+/// divergent and needs attention.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Recoverable,
+    Fatal,
+}
+
+/// A machine-readable discriminant for `ErrorResponse`, so frontends can
+/// branch on the error category instead of pattern-matching on
+/// `message`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    GraphNotFound,
+    NodeNotFound,
+    ValidationFailed,
+    PropertyWriteFailed,
+    PermissionDenied,
+    RevisionConflict,
+    NothingToUndo,
+    NothingToRedo,
+    Timeout,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ErrorResponse {
+    pub status: Status,
+    pub message: String,
+    pub error: Option<serde_json::Value>,
+    pub severity: Severity,
+    pub code: ErrorCode,
+}
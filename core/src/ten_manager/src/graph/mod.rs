@@ -0,0 +1,36 @@
+//
+// Copyright © 2025 Agora
+// This file is part of TEN Framework, an open source project.
+// Licensed under the Apache License, Version 2.0, with certain conditions.
+// Refer to the "LICENSE" file in the root directory for more information.
+//
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use ten_rust::graph::Graph;
+
+/// A loaded graph together with the bookkeeping needed to sync edits back
+/// to the `property.json` file it was parsed from.
+#[derive(Clone)]
+pub struct GraphInfo {
+    pub graph: Graph,
+    pub app_base_dir: Option<String>,
+}
+
+/// All graphs currently known to the Designer backend, keyed by graph id.
+pub type GraphsCache = HashMap<Uuid, GraphInfo>;
+
+/// Parsed package metadata for every app the Designer backend has loaded,
+/// keyed by the app's base directory. This is what `property.json`
+/// read/write helpers consult to find the file they need to sync.
+pub type PkgsCache = HashMap<String, serde_json::Value>;
+
+/// Finds a graph in the cache by id, returning a mutable reference so
+/// callers can apply in-place edits.
+pub fn graphs_cache_find_by_id_mut<'a>(
+    graphs_cache: &'a mut GraphsCache,
+    graph_id: &Uuid,
+) -> Option<&'a mut GraphInfo> {
+    graphs_cache.get_mut(graph_id)
+}